@@ -0,0 +1,129 @@
+use std::borrow::Cow;
+
+use pretty_naive_forth_evaluator::forth::Forth;
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::validate::{ValidationContext, ValidationResult, Validator};
+use rustyline::{Context, Editor, Helper, Result as RustylineResult};
+
+const OPERATORS: &[&str] = &[
+    "+", "-", "*", "/", "DUP", "DROP", "SWAP", "OVER", "<", ">", "=", "IF", "ELSE", "THEN",
+    "VARIABLE", "!", "@",
+];
+
+// Bundles the validator/highlighter/completer the REPL needs into the single
+// `Helper` rustyline expects, the same way a real Forth front-end would wire
+// them against the interpreter's own notion of "known word".
+struct ForthHelper {
+    forth: std::rc::Rc<std::cell::RefCell<Forth>>,
+}
+
+impl Helper for ForthHelper {}
+impl Hinter for ForthHelper {
+    type Hint = String;
+}
+
+impl Validator for ForthHelper {
+    fn validate(&self, ctx: &mut ValidationContext) -> RustylineResult<ValidationResult> {
+        let input = ctx.input();
+        let tokens: Vec<&str> = input.split_whitespace().collect();
+        let mut open_definitions = 0i32;
+        let mut open_conditionals = 0i32;
+        for token in &tokens {
+            match token.to_uppercase().as_str() {
+                ":" => open_definitions += 1,
+                ";" => open_definitions -= 1,
+                "IF" => open_conditionals += 1,
+                "THEN" => open_conditionals -= 1,
+                _ => {}
+            }
+        }
+        if open_definitions > 0 || open_conditionals > 0 {
+            Ok(ValidationResult::Incomplete)
+        } else {
+            Ok(ValidationResult::Valid(None))
+        }
+    }
+}
+
+impl Highlighter for ForthHelper {
+    fn highlight<'l>(&self, line: &'l str, _pos: usize) -> Cow<'l, str> {
+        let words = self.forth.borrow().word_names().iter().map(|w| w.to_string()).collect::<Vec<_>>();
+        let mut out = String::with_capacity(line.len());
+        for (i, token) in line.split_whitespace().enumerate() {
+            if i > 0 {
+                out.push(' ');
+            }
+            let upper = token.to_uppercase();
+            if OPERATORS.contains(&upper.as_str()) {
+                out.push_str(&format!("\x1b[36m{}\x1b[0m", token));
+            } else if words.contains(&upper) {
+                out.push_str(&format!("\x1b[33m{}\x1b[0m", token));
+            } else if token.parse::<i64>().is_ok() || token.parse::<f64>().is_ok() {
+                out.push_str(&format!("\x1b[32m{}\x1b[0m", token));
+            } else {
+                out.push_str(token);
+            }
+        }
+        Cow::Owned(out)
+    }
+
+    fn highlight_char(&self, _line: &str, _pos: usize) -> bool {
+        true
+    }
+}
+
+impl Completer for ForthHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> RustylineResult<(usize, Vec<Pair>)> {
+        let start = line[..pos].rfind(' ').map(|i| i + 1).unwrap_or(0);
+        let prefix = &line[start..pos];
+        let prefix_upper = prefix.to_uppercase();
+        let mut candidates: Vec<Pair> = OPERATORS
+            .iter()
+            .chain(self.forth.borrow().word_names().iter())
+            .filter(|word| word.to_uppercase().starts_with(&prefix_upper))
+            .map(|word| Pair {
+                display: word.to_string(),
+                replacement: word.to_string(),
+            })
+            .collect();
+        candidates.dedup_by(|a, b| a.replacement == b.replacement);
+        Ok((start, candidates))
+    }
+}
+
+fn main() -> RustylineResult<()> {
+    let forth = std::rc::Rc::new(std::cell::RefCell::new(Forth::new()));
+    let mut editor: Editor<ForthHelper> = Editor::new()?;
+    editor.set_helper(Some(ForthHelper {
+        forth: forth.clone(),
+    }));
+
+    loop {
+        match editor.readline("forth> ") {
+            Ok(line) => {
+                editor.add_history_entry(line.as_str());
+                let result = forth.borrow_mut().eval(&line);
+                match result {
+                    Ok(()) => println!("ok {:?}", forth.borrow().stack()),
+                    Err(err) => println!("error: {:?}", err),
+                }
+            }
+            Err(ReadlineError::Interrupted) | Err(ReadlineError::Eof) => break,
+            Err(err) => {
+                println!("error: {:?}", err);
+                break;
+            }
+        }
+    }
+    Ok(())
+}
@@ -1,12 +1,63 @@
+use std::collections::{HashMap, HashSet};
 use std::ops::{Add, Div, Mul, Sub};
+use std::rc::Rc;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Value {
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ValueType {
+    Int,
+    Float,
+    Bool,
+}
+
+impl Value {
+    fn value_type(&self) -> ValueType {
+        match self {
+            Self::Int(_) => ValueType::Int,
+            Self::Float(_) => ValueType::Float,
+            Self::Bool(_) => ValueType::Bool,
+        }
+    }
+
+    fn is_truthy(&self) -> bool {
+        match self {
+            Self::Int(n) => *n != 0,
+            Self::Float(f) => *f != 0.0,
+            Self::Bool(b) => *b,
+        }
+    }
+}
 
-pub type Value = i32;
 pub type Result = std::result::Result<(), Error>;
 type Stack = Vec<Value>;
 
+// Byte-offset range of a token in the original `eval` input.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+// `eval`'s error type: the failure plus the span of the token that caused it.
+#[derive(Debug, PartialEq)]
+pub struct PositionedError {
+    pub error: Error,
+    pub span: Span,
+}
+
+type Token<'a> = (&'a str, Span);
+
 pub struct Forth {
     stack: Stack,
     definitions: Vec<Variable>,
+    cells: HashMap<String, Value>,
+    cell_addresses: Vec<String>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -15,8 +66,11 @@ pub enum Error {
     StackUnderflow,
     UnknownWord,
     InvalidWord,
+    UnbalancedControlFlow,
+    WrongTypeCombination { expected: ValueType, actual: ValueType },
 }
 
+#[derive(Clone, Debug)]
 pub enum Operator {
     Add,
     Sub,
@@ -26,21 +80,72 @@ pub enum Operator {
     Drop,
     Swap,
     Over,
+    Less,
+    Greater,
+    Equal,
+}
+
+// A single compiled instruction; `Chunk` holds a flat program of these.
+#[derive(Clone, Debug)]
+pub enum OpCode {
+    PushConst(usize),
+    Operate(Operator),
+    CallWord(usize),
+    DeclareCell(usize),
+    CellAddress(usize),
+    Store,
+    Fetch,
+    JumpIfZero(usize),
+    Jump(usize),
 }
 
-pub enum InputValue<'a> {
-    Number(Value),
-    Operator(Operator),
-    Definition,
-    Variable(&'a Variable),
-    Void,
+// Bytecode produced by `Forth::compile`, plus the constant pools it indexes
+// into. Words hold their chunk behind an `Rc`, so calling one clones a
+// refcount rather than the instruction stream.
+#[derive(Clone, Debug, Default)]
+pub struct Chunk {
+    code: Vec<OpCode>,
+    spans: Vec<Span>,
+    constants: Vec<Value>,
+    cell_names: Vec<String>,
+}
+
+impl Chunk {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn push_op(&mut self, op: OpCode, span: Span) -> usize {
+        self.code.push(op);
+        self.spans.push(span);
+        self.code.len() - 1
+    }
+
+    fn push_constant(&mut self, value: Value) -> usize {
+        self.constants.push(value);
+        self.constants.len() - 1
+    }
+
+    fn intern_cell_name(&mut self, name: String) -> usize {
+        if let Some(index) = self.cell_names.iter().position(|n| n == &name) {
+            index
+        } else {
+            self.cell_names.push(name);
+            self.cell_names.len() - 1
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct Variable {
     word: String,
-    definition: Vec<String>,
-    definitions_index: usize,
+    chunk: Rc<Chunk>,
+}
+
+impl Default for Forth {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Forth {
@@ -48,6 +153,8 @@ impl Forth {
         Self {
             stack: Vec::new(),
             definitions: Vec::new(),
+            cells: HashMap::new(),
+            cell_addresses: Vec::new(),
         }
     }
 
@@ -55,115 +162,363 @@ impl Forth {
         &self.stack[..]
     }
 
-    pub fn eval(&mut self, input: &str) -> Result {
-        let input: Vec<&str> = input.split_whitespace().collect();
-        let mut stack: Stack = Vec::with_capacity(input.len());
+    // Currently-defined user word names, in definition order.
+    pub fn word_names(&self) -> Vec<&str> {
+        self.definitions.iter().map(|v| v.word.as_str()).collect()
+    }
+
+    pub fn eval(&mut self, input: &str) -> std::result::Result<(), PositionedError> {
+        let tokens = Self::tokenize(input);
+        let visible = self.definitions.len();
+        let chunk = self
+            .compile(&tokens, visible, &HashSet::new())
+            .map_err(|(error, span)| PositionedError { error, span })?;
+        let mut stack: Stack = Vec::with_capacity(tokens.len());
+        self.run(&chunk, &mut stack)?;
+        self.stack = stack;
+        Ok(())
+    }
+
+    // Like `split_whitespace`, but keeps each token's byte-offset span.
+    fn tokenize(input: &str) -> Vec<Token<'_>> {
+        let mut tokens = Vec::new();
+        let mut chars = input.char_indices().peekable();
+        while let Some(&(start, c)) = chars.peek() {
+            if c.is_whitespace() {
+                chars.next();
+                continue;
+            }
+            let mut end = start;
+            while let Some(&(idx, c)) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                end = idx + c.len_utf8();
+                chars.next();
+            }
+            tokens.push((&input[start..end], Span { start, end }));
+        }
+        tokens
+    }
+
+    // Compiles a token list into a standalone `Chunk`, resolving user words
+    // against `self.definitions[..visible]`. `outer_cells` carries in any
+    // `VARIABLE` names declared in the enclosing scope (e.g. a word's own
+    // body compiles separately from the input around its `: ... ;`).
+    fn compile(
+        &mut self,
+        tokens: &[Token],
+        visible: usize,
+        outer_cells: &HashSet<String>,
+    ) -> std::result::Result<Chunk, (Error, Span)> {
+        let mut chunk = Chunk::new();
+        let declared_cells: HashSet<String> = outer_cells
+            .iter()
+            .cloned()
+            .chain(Self::declared_cell_names(tokens))
+            .collect();
+        self.compile_into(&mut chunk, tokens, visible, &declared_cells)?;
+        Ok(chunk)
+    }
+
+    // Names introduced by a `VARIABLE` anywhere in `tokens`, including inside
+    // branches this compile pass won't take at run time.
+    fn declared_cell_names(tokens: &[Token]) -> HashSet<String> {
+        tokens
+            .iter()
+            .zip(tokens.iter().skip(1))
+            .filter(|(token, _)| token.0.eq_ignore_ascii_case("VARIABLE"))
+            .map(|(_, name)| name.0.to_uppercase())
+            .collect()
+    }
+
+    // Whether `token` could be a numeric literal, i.e. starts with a digit
+    // or a sign followed by one. Guards `str::parse::<f64>`, which otherwise
+    // also accepts `NaN`/`inf`/`infinity` and would silently turn a typo'd
+    // word into a float instead of an `UnknownWord` error.
+    fn looks_like_number(token: &str) -> bool {
+        let mut chars = token.chars();
+        match chars.next() {
+            Some(c) if c.is_ascii_digit() => true,
+            Some('+') | Some('-') => chars.next().is_some_and(|c| c.is_ascii_digit()),
+            _ => false,
+        }
+    }
+
+    fn compile_into(
+        &mut self,
+        chunk: &mut Chunk,
+        tokens: &[Token],
+        visible: usize,
+        declared_cells: &HashSet<String>,
+    ) -> std::result::Result<(), (Error, Span)> {
         let mut i = 0;
-        while i < input.len() {
-            let definitions = &self.definitions.clone();
-            self.evaluate_character(&mut i, &input, &mut stack, &definitions)?;
+        while i < tokens.len() {
+            let (token, span) = tokens[i];
+            let upper = token.to_uppercase();
+            match &upper[..] {
+                ":" => {
+                    let definition: Vec<Token> = tokens[i + 2..]
+                        .iter()
+                        .take_while(|token| token.0 != ";")
+                        .cloned()
+                        .collect();
+                    if !tokens[i + 1..].iter().any(|token| token.0 == ";") || definition.is_empty() {
+                        return Err((Error::InvalidWord, span));
+                    }
+                    let name = tokens.get(i + 1).ok_or((Error::InvalidWord, span))?.0;
+                    if let Some(c) = name.chars().nth(0) {
+                        if c.is_ascii_digit() {
+                            return Err((Error::InvalidWord, span));
+                        }
+                    }
+                    let word_visible = self.definitions.len();
+                    let word_chunk = self.compile(&definition, word_visible, declared_cells)?;
+                    self.definitions.push(Variable {
+                        word: name.to_uppercase(),
+                        chunk: Rc::new(word_chunk),
+                    });
+                    i += definition.len() + 3;
+                    continue;
+                }
+                "IF" => {
+                    i += 1;
+                    let (else_index, then_index) = Self::find_if_branches(tokens, i, span)?;
+                    let true_end = else_index.unwrap_or(then_index);
+                    let jump_if_zero = chunk.push_op(OpCode::JumpIfZero(0), span);
+                    self.compile_into(chunk, &tokens[i..true_end], visible, declared_cells)?;
+                    if let Some(else_index) = else_index {
+                        let (_, else_span) = tokens[else_index];
+                        let jump = chunk.push_op(OpCode::Jump(0), else_span);
+                        chunk.code[jump_if_zero] = OpCode::JumpIfZero(chunk.code.len());
+                        self.compile_into(chunk, &tokens[else_index + 1..then_index], visible, declared_cells)?;
+                        chunk.code[jump] = OpCode::Jump(chunk.code.len());
+                    } else {
+                        chunk.code[jump_if_zero] = OpCode::JumpIfZero(chunk.code.len());
+                    }
+                    i = then_index + 1;
+                    continue;
+                }
+                "VARIABLE" => {
+                    let name = tokens.get(i + 1).ok_or((Error::InvalidWord, span))?.0;
+                    if let Some(c) = name.chars().nth(0) {
+                        if c.is_ascii_digit() {
+                            return Err((Error::InvalidWord, span));
+                        }
+                    }
+                    let name_index = chunk.intern_cell_name(name.to_uppercase());
+                    chunk.push_op(OpCode::DeclareCell(name_index), span);
+                    i += 2;
+                    continue;
+                }
+                "!" => {
+                    chunk.push_op(OpCode::Store, span);
+                    i += 1;
+                    continue;
+                }
+                "@" => {
+                    chunk.push_op(OpCode::Fetch, span);
+                    i += 1;
+                    continue;
+                }
+                _ => {}
+            }
+
+            if let Some(index) = self.definitions[..visible]
+                .iter()
+                .rposition(|variable| variable.word == upper)
+            {
+                chunk.push_op(OpCode::CallWord(index), span);
+                i += 1;
+                continue;
+            }
+
+            let operator = match &upper[..] {
+                "+" => Some(Operator::Add),
+                "-" => Some(Operator::Sub),
+                "*" => Some(Operator::Mul),
+                "/" => Some(Operator::Div),
+                "DUP" => Some(Operator::Dup),
+                "DROP" => Some(Operator::Drop),
+                "SWAP" => Some(Operator::Swap),
+                "OVER" => Some(Operator::Over),
+                "<" => Some(Operator::Less),
+                ">" => Some(Operator::Greater),
+                "=" => Some(Operator::Equal),
+                _ => None,
+            };
+            if let Some(op) = operator {
+                chunk.push_op(OpCode::Operate(op), span);
+                i += 1;
+                continue;
+            }
+
+            if let Ok(number) = token.parse::<i64>() {
+                let index = chunk.push_constant(Value::Int(number));
+                chunk.push_op(OpCode::PushConst(index), span);
+                i += 1;
+                continue;
+            }
+
+            if Self::looks_like_number(token) {
+                if let Ok(number) = token.parse::<f64>() {
+                    let index = chunk.push_constant(Value::Float(number));
+                    chunk.push_op(OpCode::PushConst(index), span);
+                    i += 1;
+                    continue;
+                }
+            }
+
+            // Not a known word, operator or number: a declared cell compiles
+            // to a memory reference, anything else is a typo.
+            if !declared_cells.contains(&upper) && !self.cells.contains_key(&upper) {
+                return Err((Error::UnknownWord, span));
+            }
+            let name_index = chunk.intern_cell_name(upper);
+            chunk.push_op(OpCode::CellAddress(name_index), span);
             i += 1;
         }
-        self.stack = stack;
-        return Ok(());
+        Ok(())
+    }
+
+    // Finds the ELSE (if any) and THEN matching the IF at `start - 1`,
+    // tracking nested IF/THEN depth along the way.
+    fn find_if_branches(
+        tokens: &[Token],
+        start: usize,
+        if_span: Span,
+    ) -> std::result::Result<(Option<usize>, usize), (Error, Span)> {
+        let mut depth = 0usize;
+        let mut else_index: Option<usize> = None;
+        let mut idx = start;
+        loop {
+            if idx >= tokens.len() {
+                return Err((Error::UnbalancedControlFlow, if_span));
+            }
+            match &tokens[idx].0.to_uppercase()[..] {
+                "IF" => depth += 1,
+                "ELSE" if depth == 0 && else_index.is_none() => else_index = Some(idx),
+                "THEN" if depth == 0 => return Ok((else_index, idx)),
+                "THEN" => depth -= 1,
+                _ => {}
+            }
+            idx += 1;
+        }
     }
 
-    fn evaluate_character(
+    // Runs a compiled chunk against `stack`. Errors are tagged with the span
+    // of the instruction that raised them, re-tagged with the call site's
+    // span as they unwind out of a `CallWord`.
+    fn run(&mut self, chunk: &Chunk, stack: &mut Stack) -> std::result::Result<(), PositionedError> {
+        let mut pc = 0;
+        while pc < chunk.code.len() {
+            let span = chunk.spans[pc];
+            match self.step(chunk, pc, stack) {
+                Ok(None) => pc += 1,
+                Ok(Some(target)) => pc = target,
+                Err(error) => return Err(PositionedError { error, span }),
+            }
+        }
+        Ok(())
+    }
+
+    // Runs one instruction; `Some(target)` is a taken jump, `None` falls through.
+    fn step(
         &mut self,
-        i: &mut usize,
-        input: &Vec<&str>,
+        chunk: &Chunk,
+        pc: usize,
         stack: &mut Stack,
-        definitions: &Vec<Variable>,
-    ) -> Result {
-        // println!("i {}", i);
-        let input_value = self.evaluate_input(input.get(*i).unwrap(), definitions);
-        match input_value {
-            InputValue::Number(num) => stack.push(num),
-            InputValue::Operator(op) => {
+    ) -> std::result::Result<Option<usize>, Error> {
+        match &chunk.code[pc] {
+            OpCode::PushConst(index) => {
+                stack.push(chunk.constants[*index]);
+                Ok(None)
+            }
+            OpCode::Operate(op) => {
                 op.operate(stack)?;
+                Ok(None)
             }
-            InputValue::Definition => {
-                let inp = input.iter();
-                let definition: Vec<String> = inp
-                    .skip(*i + 2)
-                    .map(|x| String::from(*x))
-                    .take_while(|x| x != &";".to_string())
-                    .collect();
-                if None == input.iter().position(|x| x == &";") || definition.len() < 1 {
-                    return Err(Error::InvalidWord);
-                };
-                let definition_name = input.get(*i + 1).unwrap();
-                if let Some(c) = definition_name.chars().nth(0) {
-                    if c.is_digit(10) {
-                        return Err(Error::InvalidWord);
-                    }
+            OpCode::CallWord(index) => {
+                let called = self.definitions[*index].chunk.clone();
+                self.run(&called, stack).map_err(|positioned| positioned.error)?;
+                Ok(None)
+            }
+            OpCode::DeclareCell(name_index) => {
+                let name = chunk.cell_names[*name_index].clone();
+                if !self.cells.contains_key(&name) {
+                    self.cell_addresses.push(name.clone());
+                    self.cells.insert(name, Value::Int(0));
                 }
-                *i += definition.len() + 2;
-
-                self.definitions.push(Variable {
-                    word: String::from(definition_name.to_uppercase()),
-                    definition: definition.into(),
-                    definitions_index: self.definitions.len(),
-                });
-            }
-            InputValue::Variable(variable) => {
-                let mut j = 0;
-                let definition = &variable.definition;
-                let len = definition.len();
-                let definition = definition.clone();
-                let definition = definition.iter().map(|x| x.as_ref()).collect();
-                while j < len {
-                    self.evaluate_character(
-                        &mut j,
-                        &definition,
-                        stack,
-                        &self
-                            .definitions
-                            .clone()
-                            .split_at(variable.definitions_index)
-                            .0
-                            .into(),
-                    )?;
-                    j += 1;
+                Ok(None)
+            }
+            OpCode::CellAddress(name_index) => {
+                let name = &chunk.cell_names[*name_index];
+                let address = self
+                    .cell_addresses
+                    .iter()
+                    .position(|n| n == name)
+                    .ok_or(Error::UnknownWord)?;
+                stack.push(Value::Int(address as i64));
+                Ok(None)
+            }
+            OpCode::Store => {
+                if stack.len() < 2 {
+                    return Err(Error::StackUnderflow);
+                };
+                let address = stack.pop().unwrap();
+                let value = stack.pop().unwrap();
+                let address = match address {
+                    Value::Int(n) => n as usize,
+                    other => {
+                        return Err(Error::WrongTypeCombination {
+                            expected: ValueType::Int,
+                            actual: other.value_type(),
+                        })
+                    }
+                };
+                let name = self
+                    .cell_addresses
+                    .get(address)
+                    .ok_or(Error::UnknownWord)?
+                    .clone();
+                self.cells.insert(name, value);
+                Ok(None)
+            }
+            OpCode::Fetch => {
+                if stack.is_empty() {
+                    return Err(Error::StackUnderflow);
+                };
+                let address = stack.pop().unwrap();
+                let address = match address {
+                    Value::Int(n) => n as usize,
+                    other => {
+                        return Err(Error::WrongTypeCombination {
+                            expected: ValueType::Int,
+                            actual: other.value_type(),
+                        })
+                    }
+                };
+                let name = self
+                    .cell_addresses
+                    .get(address)
+                    .ok_or(Error::UnknownWord)?;
+                let value = *self.cells.get(name).ok_or(Error::UnknownWord)?;
+                stack.push(value);
+                Ok(None)
+            }
+            OpCode::JumpIfZero(target) => {
+                if stack.is_empty() {
+                    return Err(Error::StackUnderflow);
+                };
+                let condition = stack.pop().unwrap();
+                if !condition.is_truthy() {
+                    Ok(Some(*target))
+                } else {
+                    Ok(None)
                 }
             }
-            InputValue::Void => return Err(Error::UnknownWord),
+            OpCode::Jump(target) => Ok(Some(*target)),
         }
-        Ok(())
-    }
-    fn evaluate_input<'a>(&self, val: &str, definitions: &'a Vec<Variable>) -> InputValue<'a> {
-        // println!("{}", val);
-        if val == ":" {
-            return InputValue::Definition;
-        };
-        // println!("{:?}", definitions);
-        if let Some(variable) = definitions
-            .iter()
-            .rev()
-            .find(|x| x.word == val.to_uppercase())
-        {
-            return InputValue::Variable(variable);
-        }
-        let operator = match &val.to_uppercase()[..] {
-            "+" => Some(Operator::Add),
-            "-" => Some(Operator::Sub),
-            "*" => Some(Operator::Mul),
-            "/" => Some(Operator::Div),
-            "DUP" => Some(Operator::Dup),
-            "DROP" => Some(Operator::Drop),
-            "SWAP" => Some(Operator::Swap),
-            "OVER" => Some(Operator::Over),
-            _ => None,
-        };
-        if let Some(op) = operator {
-            return InputValue::Operator(op);
-        };
-
-        if let Ok(number) = val.parse::<Value>() {
-            return InputValue::Number(number);
-        };
-
-        return InputValue::Void;
     }
 }
 
@@ -176,7 +531,7 @@ impl Operator {
                 };
                 let a = stack.pop().unwrap();
                 let b = stack.pop().unwrap();
-                stack.push(a.add(b));
+                stack.push(Self::add_values(b, a)?);
                 Ok(())
             }
             Self::Sub => {
@@ -185,7 +540,7 @@ impl Operator {
                 };
                 let a = stack.pop().unwrap();
                 let b = stack.pop().unwrap();
-                stack.push(b.sub(a));
+                stack.push(Self::sub_values(b, a)?);
                 Ok(())
             }
             Self::Mul => {
@@ -194,7 +549,7 @@ impl Operator {
                 };
                 let a = stack.pop().unwrap();
                 let b = stack.pop().unwrap();
-                stack.push(b.mul(a));
+                stack.push(Self::mul_values(b, a)?);
                 Ok(())
             }
             Self::Div => {
@@ -203,15 +558,11 @@ impl Operator {
                 };
                 let a = stack.pop().unwrap();
                 let b = stack.pop().unwrap();
-
-                if a == 0 {
-                    return Err(Error::DivisionByZero);
-                };
-                stack.push(b.div(a));
+                stack.push(Self::div_values(b, a)?);
                 Ok(())
             }
             Self::Dup => {
-                if stack.len() < 1 {
+                if stack.is_empty() {
                     return Err(Error::StackUnderflow);
                 };
                 let a = stack.pop().unwrap();
@@ -220,7 +571,7 @@ impl Operator {
                 Ok(())
             }
             Self::Drop => {
-                if stack.len() < 1 {
+                if stack.is_empty() {
                     return Err(Error::StackUnderflow);
                 };
                 stack.pop().unwrap();
@@ -248,6 +599,146 @@ impl Operator {
 
                 Ok(())
             }
+            Self::Less => {
+                if stack.len() < 2 {
+                    return Err(Error::StackUnderflow);
+                };
+                let a = stack.pop().unwrap();
+                let b = stack.pop().unwrap();
+                stack.push(Value::Bool(Self::compare_values(b, a)? == std::cmp::Ordering::Less));
+                Ok(())
+            }
+            Self::Greater => {
+                if stack.len() < 2 {
+                    return Err(Error::StackUnderflow);
+                };
+                let a = stack.pop().unwrap();
+                let b = stack.pop().unwrap();
+                stack.push(Value::Bool(
+                    Self::compare_values(b, a)? == std::cmp::Ordering::Greater,
+                ));
+                Ok(())
+            }
+            Self::Equal => {
+                if stack.len() < 2 {
+                    return Err(Error::StackUnderflow);
+                };
+                let a = stack.pop().unwrap();
+                let b = stack.pop().unwrap();
+                stack.push(Value::Bool(Self::equal_values(b, a)?));
+                Ok(())
+            }
+        }
+    }
+
+    fn add_values(b: Value, a: Value) -> std::result::Result<Value, Error> {
+        match (b, a) {
+            (Value::Int(x), Value::Int(y)) => Ok(Value::Int(x.add(y))),
+            (Value::Int(x), Value::Float(y)) => Ok(Value::Float((x as f64).add(y))),
+            (Value::Float(x), Value::Int(y)) => Ok(Value::Float(x.add(y as f64))),
+            (Value::Float(x), Value::Float(y)) => Ok(Value::Float(x.add(y))),
+            (b, a) => Err(Error::WrongTypeCombination {
+                expected: b.value_type(),
+                actual: a.value_type(),
+            }),
+        }
+    }
+
+    fn sub_values(b: Value, a: Value) -> std::result::Result<Value, Error> {
+        match (b, a) {
+            (Value::Int(x), Value::Int(y)) => Ok(Value::Int(x.sub(y))),
+            (Value::Int(x), Value::Float(y)) => Ok(Value::Float((x as f64).sub(y))),
+            (Value::Float(x), Value::Int(y)) => Ok(Value::Float(x.sub(y as f64))),
+            (Value::Float(x), Value::Float(y)) => Ok(Value::Float(x.sub(y))),
+            (b, a) => Err(Error::WrongTypeCombination {
+                expected: b.value_type(),
+                actual: a.value_type(),
+            }),
+        }
+    }
+
+    fn mul_values(b: Value, a: Value) -> std::result::Result<Value, Error> {
+        match (b, a) {
+            (Value::Int(x), Value::Int(y)) => Ok(Value::Int(x.mul(y))),
+            (Value::Int(x), Value::Float(y)) => Ok(Value::Float((x as f64).mul(y))),
+            (Value::Float(x), Value::Int(y)) => Ok(Value::Float(x.mul(y as f64))),
+            (Value::Float(x), Value::Float(y)) => Ok(Value::Float(x.mul(y))),
+            (b, a) => Err(Error::WrongTypeCombination {
+                expected: b.value_type(),
+                actual: a.value_type(),
+            }),
+        }
+    }
+
+    fn div_values(b: Value, a: Value) -> std::result::Result<Value, Error> {
+        match (b, a) {
+            (Value::Int(x), Value::Int(y)) => {
+                if y == 0 {
+                    return Err(Error::DivisionByZero);
+                };
+                Ok(Value::Int(x.div(y)))
+            }
+            (Value::Int(x), Value::Float(y)) => {
+                if y == 0.0 {
+                    return Err(Error::DivisionByZero);
+                };
+                Ok(Value::Float((x as f64).div(y)))
+            }
+            (Value::Float(x), Value::Int(y)) => {
+                if y == 0 {
+                    return Err(Error::DivisionByZero);
+                };
+                Ok(Value::Float(x.div(y as f64)))
+            }
+            (Value::Float(x), Value::Float(y)) => {
+                if y == 0.0 {
+                    return Err(Error::DivisionByZero);
+                };
+                Ok(Value::Float(x.div(y)))
+            }
+            (b, a) => Err(Error::WrongTypeCombination {
+                expected: b.value_type(),
+                actual: a.value_type(),
+            }),
+        }
+    }
+
+    fn compare_values(b: Value, a: Value) -> std::result::Result<std::cmp::Ordering, Error> {
+        match (b, a) {
+            (Value::Int(x), Value::Int(y)) => Ok(x.cmp(&y)),
+            (Value::Float(x), Value::Float(y)) => {
+                x.partial_cmp(&y).ok_or(Error::WrongTypeCombination {
+                    expected: ValueType::Float,
+                    actual: ValueType::Float,
+                })
+            }
+            (Value::Int(x), Value::Float(y)) => (x as f64)
+                .partial_cmp(&y)
+                .ok_or(Error::WrongTypeCombination {
+                    expected: ValueType::Float,
+                    actual: ValueType::Float,
+                }),
+            (Value::Float(x), Value::Int(y)) => x
+                .partial_cmp(&(y as f64))
+                .ok_or(Error::WrongTypeCombination {
+                    expected: ValueType::Float,
+                    actual: ValueType::Float,
+                }),
+            (b, a) => Err(Error::WrongTypeCombination {
+                expected: b.value_type(),
+                actual: a.value_type(),
+            }),
+        }
+    }
+
+    fn equal_values(b: Value, a: Value) -> std::result::Result<bool, Error> {
+        match (b, a) {
+            (Value::Bool(x), Value::Bool(y)) => Ok(x == y),
+            (Value::Bool(_), _) | (_, Value::Bool(_)) => Err(Error::WrongTypeCombination {
+                expected: b.value_type(),
+                actual: a.value_type(),
+            }),
+            _ => Ok(Self::compare_values(b, a)? == std::cmp::Ordering::Equal),
         }
     }
 }